@@ -0,0 +1,144 @@
+//! `#[derive(ComposableQuery)]` for `edgedb_client_example`.
+//!
+//! Generates a `ComposableQuery::shape()` plus a full `Queryable` impl for a
+//! struct, auto-recursing into fields typed `Ref<T>` (a single link),
+//! `Option<Ref<T>>` (an optional link), or `Vec<Ref<T>>` (a multi link)
+//! where `T: ComposableQuery`. This replaces the hand-written
+//! `decode`/`check_descriptor` walk shown for `IsAStruct` in the main
+//! crate's `lib.rs` with generated code, the same way `edgedb_derive`'s
+//! `Queryable` does for flat structs.
+
+use proc_macro::TokenStream;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{parse_macro_input, Data, DeriveInput, Fields, GenericArgument, PathArguments, Type};
+
+#[proc_macro_derive(ComposableQuery)]
+pub fn derive_composable_query(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    let name = &input.ident;
+
+    let fields = match &input.data {
+        Data::Struct(data) => match &data.fields {
+            Fields::Named(named) => named.named.clone(),
+            _ => panic!("#[derive(ComposableQuery)] only supports structs with named fields"),
+        },
+        _ => panic!("#[derive(ComposableQuery)] only supports structs with named fields"),
+    };
+
+    let nfields = fields.len();
+    let field_idents: Vec<_> = fields.iter().map(|f| f.ident.clone().unwrap()).collect();
+    let field_types: Vec<_> = fields.iter().map(|f| f.ty.clone()).collect();
+
+    let shape_parts: Vec<TokenStream2> = fields
+        .iter()
+        .map(|field| {
+            let field_name = field.ident.as_ref().unwrap().to_string();
+            match link_inner_type(&field.ty) {
+                Some(inner) => quote! {
+                    format!(
+                        "{}: {{ {} }}",
+                        #field_name,
+                        <#inner as edgedb_client_example::ComposableQuery>::shape()
+                    )
+                },
+                None => quote! { #field_name.to_string() },
+            }
+        })
+        .collect();
+
+    let expanded = quote! {
+        impl edgedb_client_example::ComposableQuery for #name {
+            fn shape() -> String {
+                let parts: Vec<String> = vec![#(#shape_parts),*];
+                parts.join(", ")
+            }
+        }
+
+        impl edgedb_protocol::queryable::Queryable for #name {
+            fn decode(
+                decoder: &edgedb_protocol::queryable::Decoder,
+                buf: &[u8],
+            ) -> Result<Self, edgedb_protocol::errors::DecodeError> {
+                let nfields = #nfields
+                    + if decoder.has_implicit_id { 1 } else { 0 }
+                    + if decoder.has_implicit_tid { 1 } else { 0 }
+                    + if decoder.has_implicit_tname { 1 } else { 0 };
+                let mut elements =
+                    edgedb_protocol::serialization::decode::DecodeTupleLike::new_object(buf, nfields)?;
+                if decoder.has_implicit_tid {
+                    elements.skip_element()?;
+                }
+                if decoder.has_implicit_tname {
+                    elements.skip_element()?;
+                }
+                if decoder.has_implicit_id {
+                    elements.skip_element()?;
+                }
+                #(
+                    let #field_idents: #field_types =
+                        edgedb_protocol::queryable::Queryable::decode_optional(decoder, elements.read()?)?;
+                )*
+                Ok(#name { #(#field_idents),* })
+            }
+
+            fn check_descriptor(
+                ctx: &edgedb_protocol::queryable::DescriptorContext,
+                type_pos: edgedb_protocol::descriptors::TypePos,
+            ) -> Result<(), edgedb_protocol::queryable::DescriptorMismatch> {
+                let desc = ctx.get(type_pos)?;
+                let shape = match desc {
+                    edgedb_protocol::descriptors::Descriptor::ObjectShape(shape) => shape,
+                    _ => return Err(ctx.wrong_type(desc, stringify!(#name))),
+                };
+                let mut idx = 0;
+                if ctx.has_implicit_tid {
+                    idx += 1;
+                }
+                if ctx.has_implicit_tname {
+                    idx += 1;
+                }
+                if ctx.has_implicit_id {
+                    idx += 1;
+                }
+                #(
+                    let field_name = stringify!(#field_idents);
+                    let el = &shape.elements[idx];
+                    if el.name != field_name {
+                        return Err(ctx.wrong_field(field_name, &el.name));
+                    }
+                    idx += 1;
+                    <#field_types as edgedb_protocol::queryable::Queryable>::check_descriptor(ctx, el.type_pos)?;
+                )*
+                if shape.elements.len() != idx {
+                    return Err(ctx.field_number(shape.elements.len(), idx));
+                }
+                Ok(())
+            }
+        }
+    };
+
+    TokenStream::from(expanded)
+}
+
+// Recognizes `Ref<T>`, `Option<Ref<T>>`, and `Vec<Ref<T>>` as link fields
+// and returns `T`; everything else (`String`, `i16`, `Option<String>`, ...)
+// is treated as a plain scalar field and returns `None`.
+fn link_inner_type(ty: &Type) -> Option<TokenStream2> {
+    let Type::Path(type_path) = ty else {
+        return None;
+    };
+    let segment = type_path.path.segments.last()?;
+    let PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let GenericArgument::Type(inner_ty) = args.args.first()? else {
+        return None;
+    };
+
+    match segment.ident.to_string().as_str() {
+        "Ref" => Some(quote! { #inner_ty }),
+        "Option" | "Vec" => link_inner_type(inner_ty),
+        _ => None,
+    }
+}
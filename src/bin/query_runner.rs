@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::io::Write;
+
+use argh::FromArgs;
+use edgedb_protocol::value::Value;
+use tabwriter::TabWriter;
+
+use edgedb_client_example::NamedQuery;
+
+/// A small interactive query runner for poking at an EdgeDB instance
+/// without recompiling the examples in main.rs.
+#[derive(FromArgs)]
+struct Cli {
+    #[argh(subcommand)]
+    command: Command,
+
+    /// print results as JSON instead of an aligned table
+    #[argh(switch)]
+    json: bool,
+}
+
+#[derive(FromArgs)]
+#[argh(subcommand)]
+enum Command {
+    Query(QueryCmd),
+    QueryJson(QueryJsonCmd),
+    Tx(TxCmd),
+}
+
+/// run a single EdgeQL query and print its result
+#[argh(subcommand, name = "query")]
+#[derive(FromArgs)]
+struct QueryCmd {
+    /// the EdgeQL query text
+    #[argh(positional)]
+    edgeql: String,
+
+    /// named arguments as name[:type]=value pairs (type defaults to str),
+    /// e.g. --arg username=Alice --arg first:int32=10
+    #[argh(option)]
+    arg: Vec<String>,
+}
+
+/// run a single EdgeQL query and print its result as JSON
+#[argh(subcommand, name = "query-json")]
+#[derive(FromArgs)]
+struct QueryJsonCmd {
+    /// the EdgeQL query text
+    #[argh(positional)]
+    edgeql: String,
+
+    /// named arguments as name[:type]=value pairs (type defaults to str),
+    /// e.g. --arg username=Alice --arg first:int32=10
+    #[argh(option)]
+    arg: Vec<String>,
+}
+
+/// run a sequence of EdgeQL statements in a single transaction
+#[argh(subcommand, name = "tx")]
+#[derive(FromArgs)]
+struct TxCmd {
+    /// one or more EdgeQL statements to run in order
+    #[argh(positional)]
+    edgeql: Vec<String>,
+
+    /// named arguments shared by every statement, as name[:type]=value
+    /// pairs (type defaults to str), e.g. --arg id:uuid=...
+    #[argh(option)]
+    arg: Vec<String>,
+}
+
+// `--arg` values are `name[:type]=value`; `type` defaults to `str` when
+// omitted, matching the single-quoted-string examples in main.rs. Typed
+// arguments are needed to drive queries with non-`str` casts, such as
+// main.rs's `<str>$arg1, <int32>$arg2`.
+fn parse_args(pairs: &[String]) -> anyhow::Result<HashMap<String, Value>> {
+    pairs
+        .iter()
+        .map(|pair| {
+            let (name_and_type, raw_value) = pair
+                .split_once('=')
+                .ok_or_else(|| anyhow::anyhow!("--arg `{pair}` must be of the form name[:type]=value"))?;
+            let (name, ty) = match name_and_type.split_once(':') {
+                Some((name, ty)) => (name, ty),
+                None => (name_and_type, "str"),
+            };
+            let value = parse_typed_value(ty, raw_value)
+                .map_err(|e| anyhow::anyhow!("--arg `{pair}`: {e}"))?;
+            Ok((name.to_string(), value))
+        })
+        .collect()
+}
+
+fn parse_typed_value(ty: &str, raw: &str) -> anyhow::Result<Value> {
+    Ok(match ty {
+        "str" => Value::Str(raw.to_string()),
+        "bool" => Value::Bool(raw.parse()?),
+        "int16" => Value::Int16(raw.parse()?),
+        "int32" => Value::Int32(raw.parse()?),
+        "int64" => Value::Int64(raw.parse()?),
+        "float32" => Value::Float32(raw.parse()?),
+        "float64" => Value::Float64(raw.parse()?),
+        "uuid" => Value::Uuid(raw.parse()?),
+        other => anyhow::bail!(
+            "unsupported type `{other}` (expected one of str, bool, int16, int32, int64, \
+             float32, float64, uuid)"
+        ),
+    })
+}
+
+// Renders strings, bools, ints, floats and UUIDs the way a user would type
+// them (`Alice`, not `Str("Alice")`); every other value, compound (objects,
+// tuples, arrays) or scalar (`BigInt`, `Decimal`, dates, ...), renders via
+// `Debug`.
+fn render_cell(value: &Value) -> String {
+    match value {
+        Value::Nothing => String::new(),
+        Value::Str(s) => s.clone(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int16(n) => n.to_string(),
+        Value::Int32(n) => n.to_string(),
+        Value::Int64(n) => n.to_string(),
+        Value::Float32(n) => n.to_string(),
+        Value::Float64(n) => n.to_string(),
+        Value::Uuid(id) => id.to_string(),
+        Value::BigInt(n) => format!("{n:?}"),
+        other => format!("{other:?}"),
+    }
+}
+
+// Prints a `Vec<Value::Object>` as an aligned table, the way mentat pipes
+// rows through a `TabWriter`: headers come from the shape's element names,
+// each field renders via `render_cell`, and columns line up via tabs.
+fn print_table(rows: &[Value]) -> anyhow::Result<()> {
+    let mut tw = TabWriter::new(std::io::stdout());
+    let mut printed_header = false;
+    for row in rows {
+        if let Value::Object { shape, fields } = row {
+            if !printed_header {
+                let headers: Vec<&str> = shape.elements.iter().map(|el| el.name.as_str()).collect();
+                writeln!(tw, "{}", headers.join("\t"))?;
+                printed_header = true;
+            }
+            let cells: Vec<String> = fields
+                .iter()
+                .map(|field| match field {
+                    Some(value) => render_cell(value),
+                    None => String::new(),
+                })
+                .collect();
+            writeln!(tw, "{}", cells.join("\t"))?;
+        } else {
+            eprintln!("warning: row is not an object, skipping table formatting: {row:?}");
+            println!("{row:?}");
+        }
+    }
+    tw.flush()?;
+    Ok(())
+}
+
+#[tokio::main]
+async fn main() -> anyhow::Result<()> {
+    let cli: Cli = argh::from_env();
+    let client = edgedb_tokio::create_client().await?;
+
+    match cli.command {
+        Command::Query(cmd) => {
+            let named = NamedQuery::new(&cmd.edgeql);
+            let args = parse_args(&cmd.arg)?;
+            if cli.json {
+                let json = named.query_json(&client, &args).await?;
+                println!("{json}");
+            } else {
+                let rows: Vec<Value> = named.query(&client, &args).await?;
+                print_table(&rows)?;
+            }
+        }
+        Command::QueryJson(cmd) => {
+            let named = NamedQuery::new(&cmd.edgeql);
+            let args = parse_args(&cmd.arg)?;
+            let json = named.query_json(&client, &args).await?;
+            println!("{json}");
+        }
+        Command::Tx(cmd) => {
+            let args = parse_args(&cmd.arg)?;
+            client
+                .transaction(|mut conn| {
+                    let statements = cmd.edgeql.clone();
+                    let args = args.clone();
+                    async move {
+                        for statement in &statements {
+                            let named = NamedQuery::new(statement);
+                            let positional = named.positional_args(&args)?;
+                            conn.execute(named.rewritten(), &positional).await?;
+                        }
+                        Ok(())
+                    }
+                })
+                .await?;
+            eprintln!("transaction committed ({} statement(s))", cmd.edgeql.len());
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,127 @@
+use edgedb_protocol::queryable::Queryable;
+use edgedb_protocol::query_arg::QueryArgs;
+use edgedb_tokio::{Client, Error};
+use tokio::runtime::{Handle, Runtime};
+
+use crate::error::local_error;
+
+// Tutorial aside in main.rs notes that async-averse callers can bridge with
+// `rt.block_on(...)` by hand. `BlockingClient` packages that up: it owns its
+// own Tokio runtime plus an `edgedb_tokio::Client` and exposes synchronous
+// equivalents of the calls used throughout this crate.
+//
+// `transaction`'s closure is synchronous (it calls `BlockingTransaction`
+// methods, not `.await`), but it runs *inside* the `block_on` call that
+// drives `self.inner.transaction(...)`. Calling `block_on` a second time
+// from in there would be the reentrant-runtime case Tokio panics on
+// ("Cannot start a runtime from within a runtime"), so the per-query calls
+// on `BlockingTransaction` instead go through `tokio::task::block_in_place`,
+// which suspends the current worker thread to allow a nested `block_on`
+// safely. That requires a multi-thread runtime (`block_in_place` panics on
+// a current-thread one), so this owns a multi-thread `Runtime` rather than
+// a current-thread one.
+pub struct BlockingClient {
+    rt: Runtime,
+    inner: Client,
+}
+
+impl BlockingClient {
+    pub fn new() -> Result<Self, Error> {
+        let rt = tokio::runtime::Builder::new_multi_thread()
+            .enable_all()
+            .build()
+            .map_err(|e| local_error(format!("failed to start Tokio runtime: {e}")))?;
+        let inner = rt.block_on(edgedb_tokio::create_client())?;
+        Ok(BlockingClient { rt, inner })
+    }
+
+    // `block_on` panics if called from within an existing Tokio context
+    // (you can't nest runtimes). Detect that case up front and return an
+    // error instead, so this facade is safe to drop into an otherwise-sync
+    // program without the caller needing to know our internals.
+    fn ensure_no_nested_runtime(&self) -> Result<(), Error> {
+        if Handle::try_current().is_ok() {
+            return Err(local_error(
+                "BlockingClient called from within an async context; \
+                 use edgedb_tokio::Client directly instead",
+            ));
+        }
+        Ok(())
+    }
+
+    pub fn query<T, A>(&self, query: &str, args: &A) -> Result<Vec<T>, Error>
+    where
+        T: Queryable + Send,
+        A: QueryArgs,
+    {
+        self.ensure_no_nested_runtime()?;
+        self.rt.block_on(self.inner.query(query, args))
+    }
+
+    pub fn query_required_single<T, A>(&self, query: &str, args: &A) -> Result<T, Error>
+    where
+        T: Queryable + Send,
+        A: QueryArgs,
+    {
+        self.ensure_no_nested_runtime()?;
+        self.rt.block_on(self.inner.query_required_single(query, args))
+    }
+
+    pub fn query_single_json<A>(&self, query: &str, args: &A) -> Result<Option<String>, Error>
+    where
+        A: QueryArgs,
+    {
+        self.ensure_no_nested_runtime()?;
+        self.rt
+            .block_on(self.inner.query_single_json(query, args))
+            .map(|json| json.map(|j| j.to_string()))
+    }
+
+    // `body` runs synchronously; internally it is handed a blocking wrapper
+    // around the transaction's connection so the closure never has to deal
+    // with `.await` itself. `body` itself runs via `block_in_place` (see the
+    // struct doc comment) so its `BlockingTransaction` calls can safely
+    // nest another `block_on` without panicking.
+    pub fn transaction<T, F>(&self, mut body: F) -> Result<T, Error>
+    where
+        F: FnMut(&mut BlockingTransaction) -> Result<T, Error>,
+        T: Send + 'static,
+    {
+        self.ensure_no_nested_runtime()?;
+        let handle = self.rt.handle().clone();
+        self.rt.block_on(self.inner.transaction(|conn| {
+            let handle = handle.clone();
+            async move {
+                let mut tx = BlockingTransaction { handle, conn };
+                tokio::task::block_in_place(|| body(&mut tx))
+            }
+        }))
+    }
+}
+
+// Handed to the closure passed to `BlockingClient::transaction` so queries
+// issued inside the transaction are also blocking calls. Only constructed
+// from inside `tokio::task::block_in_place`, so `handle.block_on` below is
+// a safe nested call rather than the reentrant-runtime panic case.
+pub struct BlockingTransaction {
+    handle: Handle,
+    conn: edgedb_tokio::Transaction,
+}
+
+impl BlockingTransaction {
+    pub fn query<T, A>(&mut self, query: &str, args: &A) -> Result<Vec<T>, Error>
+    where
+        T: Queryable + Send,
+        A: QueryArgs,
+    {
+        self.handle.block_on(self.conn.query(query, args))
+    }
+
+    pub fn query_required_single<T, A>(&mut self, query: &str, args: &A) -> Result<T, Error>
+    where
+        T: Queryable + Send,
+        A: QueryArgs,
+    {
+        self.handle.block_on(self.conn.query_required_single(query, args))
+    }
+}
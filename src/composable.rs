@@ -0,0 +1,82 @@
+use edgedb_protocol::{
+    descriptors::TypePos,
+    errors::DecodeError,
+    queryable::{Decoder, DescriptorContext, DescriptorMismatch, Queryable},
+};
+
+pub use edgedb_client_example_derive::ComposableQuery;
+
+// `IsAStruct` in lib.rs shows the flat shape `Queryable` generates by hand.
+// `ComposableQuery` extends the same idea to nested object links, the way
+// edgedb-composable-query's `EdgedbObject` + `Ref<T>` do: a struct can hold
+// a `Ref<Other>` field and `#[derive(ComposableQuery)]` splices `Other`'s
+// own shape/decode/check_descriptor into the parent's rather than requiring
+// it to be hand-written per struct.
+
+// Wraps a linked object so the derive can tell a scalar field apart from a
+// link that needs its own nested shape. `Option<Ref<T>>` is an optional
+// link, `Vec<Ref<T>>` a multi link - both work for free because `Ref<T>`
+// itself implements `Queryable` below, and `edgedb_protocol` already
+// implements `Queryable` for `Option<U>`/`Vec<U>` in terms of `U`.
+#[derive(Debug)]
+pub struct Ref<T>(pub T);
+
+impl<T> std::ops::Deref for Ref<T> {
+    type Target = T;
+    fn deref(&self) -> &T {
+        &self.0
+    }
+}
+
+impl<T: ComposableQuery> Queryable for Ref<T> {
+    fn decode(decoder: &Decoder, buf: &[u8]) -> Result<Self, DecodeError> {
+        Ok(Ref(T::decode(decoder, buf)?))
+    }
+
+    fn check_descriptor(ctx: &DescriptorContext, type_pos: TypePos) -> Result<(), DescriptorMismatch> {
+        T::check_descriptor(ctx, type_pos)
+    }
+}
+
+// Implemented by `#[derive(ComposableQuery)]` (see `edgedb_client_example_derive`).
+// `shape()` produces the `field_a, field_b, link: { ... }` selection text
+// (recursing into any field typed `Ref<T>`/`Option<Ref<T>>`/`Vec<Ref<T>>`
+// where `T: ComposableQuery`), and the derive also implements `Queryable`
+// so nested links decode inline rather than requiring a second round-trip.
+pub trait ComposableQuery: Queryable {
+    fn shape() -> String;
+
+    // Convenience for the top-level call site:
+    // `client.query_required_single(&Outer::shape_query("Outer"), &()).await?`
+    fn shape_query(type_name: &str) -> String {
+        format!("select {type_name} {{ {} }}", Self::shape())
+    }
+}
+
+// Example from the request: a struct with a link field derives its shape
+// and `Queryable` impl instead of hand-writing them. `IsAStruct` already
+// has a hand-written `Queryable` impl in lib.rs, and implements
+// `ComposableQuery` there too so it can be nested as a link.
+#[derive(Debug, ComposableQuery)]
+pub struct Outer {
+    pub name: String,
+    pub inner: Ref<super::IsAStruct>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn derive_generates_a_nested_shape() {
+        assert_eq!(Outer::shape(), "name, inner: { name, number, is_cool }");
+    }
+
+    #[test]
+    fn shape_query_wraps_the_shape_in_a_select() {
+        assert_eq!(
+            Outer::shape_query("Outer"),
+            "select Outer { name, inner: { name, number, is_cool } }"
+        );
+    }
+}
@@ -0,0 +1,126 @@
+use edgedb_tokio::Error;
+
+// EdgeDB tags every error with a 32-bit code whose high bits group errors
+// into families (e.g. all TransactionConflictError subtypes share the same
+// leading byte). This mirrors the way rust-postgres turns a SQLSTATE string
+// into a typed `SqlState` enum: instead of matching on `format!("{err:?}")`
+// (as the rest of this crate's examples do further up in main.rs), match on
+// `EdgeDbErrorCode` directly.
+//
+// The numeric tags below come from EdgeDB's `edb/errors/base.py` error
+// table; only the variants this crate currently cares about are named, the
+// rest fall back to `Other`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum EdgeDbErrorCode {
+    TransactionConflict,
+    TransactionSerializationError,
+    TransactionDeadlockError,
+    AccessError,
+    SchemaError,
+    QueryError,
+    InvalidValueError,
+    DivisionByZeroError,
+    Other(u32),
+}
+
+// (code, variant) table used both to build the lookup below and as the
+// single source of truth if new codes need to be added later.
+const CODE_TABLE: &[(u32, EdgeDbErrorCode)] = &[
+    (0x_05_03_01_00, EdgeDbErrorCode::TransactionConflict),
+    (0x_05_03_01_01, EdgeDbErrorCode::TransactionSerializationError),
+    (0x_05_03_01_02, EdgeDbErrorCode::TransactionDeadlockError),
+    (0x_07_00_00_00, EdgeDbErrorCode::AccessError),
+    (0x_04_04_00_00, EdgeDbErrorCode::SchemaError),
+    (0x_04_00_00_00, EdgeDbErrorCode::QueryError),
+    (0x_05_01_00_00, EdgeDbErrorCode::InvalidValueError),
+    (0x_05_01_00_01, EdgeDbErrorCode::DivisionByZeroError),
+];
+
+impl EdgeDbErrorCode {
+    pub fn from_code(code: u32) -> Self {
+        CODE_TABLE
+            .iter()
+            .find(|(c, _)| *c == code)
+            .map(|(_, variant)| *variant)
+            .unwrap_or(EdgeDbErrorCode::Other(code))
+    }
+
+    pub fn is_transaction_conflict(self) -> bool {
+        matches!(
+            self,
+            EdgeDbErrorCode::TransactionConflict
+                | EdgeDbErrorCode::TransactionSerializationError
+                | EdgeDbErrorCode::TransactionDeadlockError
+        )
+    }
+
+    // Whether a client is expected to retry the query/transaction that
+    // produced this error, mirroring the `RetryCondition` block commented
+    // out at the bottom of main.rs.
+    pub fn is_retryable(self) -> bool {
+        self.is_transaction_conflict()
+    }
+}
+
+impl From<&Error> for EdgeDbErrorCode {
+    fn from(err: &Error) -> Self {
+        EdgeDbErrorCode::from_code(err.code())
+    }
+}
+
+impl From<Error> for EdgeDbErrorCode {
+    fn from(err: Error) -> Self {
+        EdgeDbErrorCode::from(&err)
+    }
+}
+
+// `NamedQuery`, `paginate`, and `BlockingClient` can all fail before a query
+// ever reaches the server (a missing named argument, a malformed cursor, a
+// runtime that refused to start). None of those have one of EdgeDB's real
+// error codes, so rather than each module inventing its own sentinel,
+// route every client-side validation failure through this single helper.
+pub(crate) fn local_error(message: impl std::fmt::Display) -> Error {
+    Error::from_code(0).context(message.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_code_maps_known_codes_to_their_variant() {
+        assert_eq!(
+            EdgeDbErrorCode::from_code(0x_05_03_01_00),
+            EdgeDbErrorCode::TransactionConflict
+        );
+        assert_eq!(
+            EdgeDbErrorCode::from_code(0x_05_03_01_01),
+            EdgeDbErrorCode::TransactionSerializationError
+        );
+        assert_eq!(
+            EdgeDbErrorCode::from_code(0x_05_01_00_01),
+            EdgeDbErrorCode::DivisionByZeroError
+        );
+    }
+
+    #[test]
+    fn from_code_falls_back_to_other_for_unknown_codes() {
+        assert_eq!(EdgeDbErrorCode::from_code(0xdead_beef), EdgeDbErrorCode::Other(0xdead_beef));
+    }
+
+    #[test]
+    fn is_transaction_conflict_covers_all_transaction_conflict_subtypes() {
+        assert!(EdgeDbErrorCode::TransactionConflict.is_transaction_conflict());
+        assert!(EdgeDbErrorCode::TransactionSerializationError.is_transaction_conflict());
+        assert!(EdgeDbErrorCode::TransactionDeadlockError.is_transaction_conflict());
+        assert!(!EdgeDbErrorCode::AccessError.is_transaction_conflict());
+        assert!(!EdgeDbErrorCode::Other(0).is_transaction_conflict());
+    }
+
+    #[test]
+    fn is_retryable_matches_is_transaction_conflict() {
+        assert!(EdgeDbErrorCode::from_code(0x_05_03_01_00).is_retryable());
+        assert!(!EdgeDbErrorCode::from_code(0x_07_00_00_00).is_retryable());
+        assert!(!EdgeDbErrorCode::QueryError.is_retryable());
+    }
+}
@@ -5,6 +5,25 @@ use edgedb_protocol::{
     serialization::decode::DecodeTupleLike,
 };
 
+pub mod error;
+pub use error::EdgeDbErrorCode;
+
+pub mod named_query;
+pub use named_query::NamedQuery;
+
+// Lets `#[derive(ComposableQuery)]`'s `edgedb_client_example::` paths resolve
+// inside this crate too (see `composable::Outer`).
+extern crate self as edgedb_client_example;
+
+pub mod composable;
+pub use composable::{ComposableQuery, Ref};
+
+pub mod pagination;
+pub use pagination::{paginate, Connection, Edge, HasId, PageInfo};
+
+pub mod blocking;
+pub use blocking::{BlockingClient, BlockingTransaction};
+
 // The code below shows the code generated from the Queryable macro in a more readable form
 // (with macro-generated qualified paths replaced with use statements).
 
@@ -93,3 +112,11 @@ impl Queryable for IsAStruct {
         Ok(())
     }
 }
+
+// Lets `IsAStruct` be nested as a `Ref<IsAStruct>` link field by another
+// `#[derive(ComposableQuery)]` struct (see `composable::Outer`).
+impl ComposableQuery for IsAStruct {
+    fn shape() -> String {
+        "name, number, is_cool".to_string()
+    }
+}
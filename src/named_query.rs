@@ -0,0 +1,177 @@
+use std::collections::HashMap;
+
+use edgedb_protocol::model::Json;
+use edgedb_protocol::value::Value;
+use edgedb_tokio::{Client, Error};
+
+use crate::error::local_error;
+
+// EdgeQL itself accepts named arguments (`<str>$arg1`) but, as shown in
+// main.rs, the Rust client only accepts positional arguments ($0, $1, ...).
+// `NamedQuery` bridges the two: it scans the query text for `$ident` tokens
+// (mirroring async-graphql's `Variables`, a name -> Value map), rewrites
+// them to positional form, and reorders the caller's arguments to match.
+pub struct NamedQuery {
+    rewritten: String,
+    names: Vec<String>,
+}
+
+impl NamedQuery {
+    // Parses `query`, replacing every distinct `$name` (optionally preceded
+    // by a cast like `<int32>$name`) with `$0`, `$1`, ... in order of first
+    // appearance. Repeated uses of the same name reuse the same index.
+    // Names inside string literals are left untouched.
+    pub fn new(query: &str) -> Self {
+        let mut rewritten = String::with_capacity(query.len());
+        let mut names: Vec<String> = Vec::new();
+        let mut in_string: Option<char> = None;
+        let chars: Vec<char> = query.chars().collect();
+        let mut i = 0;
+        while i < chars.len() {
+            let c = chars[i];
+            if let Some(quote) = in_string {
+                rewritten.push(c);
+                if c == '\\' && i + 1 < chars.len() {
+                    i += 1;
+                    rewritten.push(chars[i]);
+                } else if c == quote {
+                    in_string = None;
+                }
+                i += 1;
+                continue;
+            }
+            if c == '\'' || c == '"' {
+                in_string = Some(c);
+                rewritten.push(c);
+                i += 1;
+                continue;
+            }
+            if c == '$' && i + 1 < chars.len() && (chars[i + 1].is_alphabetic() || chars[i + 1] == '_') {
+                let start = i + 1;
+                let mut end = start;
+                while end < chars.len() && (chars[end].is_alphanumeric() || chars[end] == '_') {
+                    end += 1;
+                }
+                let name: String = chars[start..end].iter().collect();
+                let index = match names.iter().position(|n| n == &name) {
+                    Some(pos) => pos,
+                    None => {
+                        names.push(name);
+                        names.len() - 1
+                    }
+                };
+                rewritten.push('$');
+                rewritten.push_str(&index.to_string());
+                i = end;
+                continue;
+            }
+            rewritten.push(c);
+            i += 1;
+        }
+        NamedQuery { rewritten, names }
+    }
+
+    // The query text with every `$name` rewritten to its positional slot.
+    // Exposed so callers that can't go through `query`/`query_required_single`
+    // (e.g. `conn.execute` inside a transaction) can still reuse the rewrite.
+    pub fn rewritten(&self) -> &str {
+        &self.rewritten
+    }
+
+    // Orders `args` (keyed by the same names found in the query) into the
+    // positional sequence the rewritten query expects. Returns an error if
+    // the query references a name that is missing from `args`.
+    pub fn positional_args(&self, args: &HashMap<String, Value>) -> Result<Vec<Value>, Error> {
+        self.names
+            .iter()
+            .map(|name| {
+                args.get(name).cloned().ok_or_else(|| {
+                    local_error(format!(
+                        "NamedQuery: missing argument `{name}` for query `{}`",
+                        self.rewritten
+                    ))
+                })
+            })
+            .collect()
+    }
+
+    pub async fn query<T>(&self, client: &Client, args: &HashMap<String, Value>) -> Result<Vec<T>, Error>
+    where
+        T: edgedb_protocol::queryable::Queryable + Send,
+    {
+        let positional = self.positional_args(args)?;
+        client.query(&self.rewritten, &positional).await
+    }
+
+    pub async fn query_required_single<T>(
+        &self,
+        client: &Client,
+        args: &HashMap<String, Value>,
+    ) -> Result<T, Error>
+    where
+        T: edgedb_protocol::queryable::Queryable + Send,
+    {
+        let positional = self.positional_args(args)?;
+        client.query_required_single(&self.rewritten, &positional).await
+    }
+
+    pub async fn query_json(&self, client: &Client, args: &HashMap<String, Value>) -> Result<Json, Error> {
+        let positional = self.positional_args(args)?;
+        client.query_json(&self.rewritten, &positional).await
+    }
+
+    pub async fn query_single_json(
+        &self,
+        client: &Client,
+        args: &HashMap<String, Value>,
+    ) -> Result<Option<Json>, Error> {
+        let positional = self.positional_args(args)?;
+        client.query_single_json(&self.rewritten, &positional).await
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn rewrites_distinct_names_in_order_of_first_appearance() {
+        let q = NamedQuery::new("select {(<str>$arg1, <int32>$arg2)};");
+        assert_eq!(q.rewritten(), "select {(<str>$0, <int32>$1)};");
+        assert_eq!(q.names, vec!["arg1".to_string(), "arg2".to_string()]);
+    }
+
+    #[test]
+    fn reuses_the_same_index_for_repeated_names() {
+        let q = NamedQuery::new("select (<str>$name, <str>$name);");
+        assert_eq!(q.rewritten(), "select (<str>$0, <str>$0);");
+        assert_eq!(q.names, vec!["name".to_string()]);
+    }
+
+    #[test]
+    fn leaves_names_inside_string_literals_untouched() {
+        let q = NamedQuery::new(r#"select ("$not_an_arg", <str>$real)"#);
+        assert_eq!(q.rewritten(), r#"select ("$not_an_arg", <str>$0)"#);
+        assert_eq!(q.names, vec!["real".to_string()]);
+    }
+
+    #[test]
+    fn positional_args_orders_by_first_appearance() {
+        let q = NamedQuery::new("select (<str>$b, <str>$a);");
+        let mut args = HashMap::new();
+        args.insert("a".to_string(), Value::Str("A".to_string()));
+        args.insert("b".to_string(), Value::Str("B".to_string()));
+        let positional = q.positional_args(&args).unwrap();
+        assert_eq!(
+            positional,
+            vec![Value::Str("B".to_string()), Value::Str("A".to_string())]
+        );
+    }
+
+    #[test]
+    fn positional_args_errors_on_missing_name() {
+        let q = NamedQuery::new("select <str>$missing;");
+        let args = HashMap::new();
+        assert!(q.positional_args(&args).is_err());
+    }
+}
@@ -0,0 +1,133 @@
+use base64::{engine::general_purpose::STANDARD, Engine as _};
+use edgedb_protocol::queryable::Queryable;
+use edgedb_tokio::{Client, Error};
+use uuid::Uuid;
+
+use crate::error::local_error;
+
+// Relay-style keyset pagination over an EdgeDB object set, modeled on
+// async-graphql's Cursor Connections spec. Rather than `offset`/`limit`
+// (which re-scans skipped rows on every page), `paginate` filters on
+// `.id > <uuid>$after` so each page is a stable, indexable jump from the
+// previous one.
+
+pub struct Edge<T> {
+    pub node: T,
+    pub cursor: String,
+}
+
+#[derive(Debug, Clone, Default)]
+pub struct PageInfo {
+    pub has_next_page: bool,
+    pub has_previous_page: bool,
+    pub start_cursor: Option<String>,
+    pub end_cursor: Option<String>,
+}
+
+pub struct Connection<T> {
+    pub edges: Vec<Edge<T>>,
+    pub page_info: PageInfo,
+}
+
+// Any row type usable with `paginate` must carry the object's `id` so a
+// cursor can be derived from it, in addition to being `Queryable`.
+pub trait HasId: Queryable {
+    fn id(&self) -> Uuid;
+}
+
+fn encode_cursor(id: Uuid) -> String {
+    STANDARD.encode(id.as_bytes())
+}
+
+fn decode_cursor(cursor: &str) -> Result<Uuid, Error> {
+    let bytes = STANDARD
+        .decode(cursor)
+        .map_err(|e| local_error(format!("invalid cursor `{cursor}`: {e}")))?;
+    Uuid::from_slice(&bytes).map_err(|e| local_error(format!("invalid cursor `{cursor}`: {e}")))
+}
+
+// `base_select` is the user's select without `order by`/`filter`/`limit`,
+// e.g. `"Account { id, username }"`. `paginate` appends
+// `filter .id > <uuid>$0` (when `after` is given, bound to its decoded id),
+// `order by .id` and `limit <int32>$N + 1` (with `$N` bound to `first`),
+// fetches one extra row to detect `has_next_page`, then drops it before
+// returning.
+pub async fn paginate<T>(
+    client: &Client,
+    base_select: &str,
+    first: i32,
+    after: Option<&str>,
+) -> Result<Connection<T>, Error>
+where
+    T: HasId + Send,
+{
+    if first < 0 {
+        return Err(local_error(format!(
+            "paginate: `first` must not be negative, got {first}"
+        )));
+    }
+
+    let after_id = after.map(decode_cursor).transpose()?;
+
+    let query = match after_id {
+        Some(_) => format!(
+            "select {base_select} filter .id > <uuid>$0 order by .id limit <int32>$1 + 1"
+        ),
+        None => format!("select {base_select} order by .id limit <int32>$0 + 1"),
+    };
+
+    let mut rows: Vec<T> = match after_id {
+        Some(after_id) => client.query(&query, &(after_id, first)).await?,
+        None => client.query(&query, &(first,)).await?,
+    };
+
+    let has_next_page = rows.len() > first as usize;
+    if has_next_page {
+        rows.truncate(first as usize);
+    }
+
+    let start_cursor = rows.first().map(|row| encode_cursor(row.id()));
+    let end_cursor = rows.last().map(|row| encode_cursor(row.id()));
+
+    let edges = rows
+        .into_iter()
+        .map(|row| {
+            let cursor = encode_cursor(row.id());
+            Edge { node: row, cursor }
+        })
+        .collect();
+
+    Ok(Connection {
+        edges,
+        page_info: PageInfo {
+            has_next_page,
+            has_previous_page: after_id.is_some(),
+            start_cursor,
+            end_cursor,
+        },
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn cursor_round_trips_through_encode_and_decode() {
+        let id = Uuid::new_v4();
+        let cursor = encode_cursor(id);
+        assert_eq!(decode_cursor(&cursor).unwrap(), id);
+    }
+
+    #[test]
+    fn decode_cursor_rejects_invalid_base64() {
+        assert!(decode_cursor("not valid base64!!").is_err());
+    }
+
+    #[test]
+    fn decode_cursor_rejects_base64_of_the_wrong_length() {
+        // Valid base64, but not 16 bytes once decoded, so it can't be a Uuid.
+        let short = STANDARD.encode(b"too short");
+        assert!(decode_cursor(&short).is_err());
+    }
+}